@@ -35,6 +35,121 @@
 //! }
 //! ```
 //!
+//! # Deep comparison for selected variants
+//!
+//! Mark individual variants with `#[varianteq(deep)]` to compare their fields for real while all
+//! other variants keep discriminant-only equality.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate varianteq;
+//!
+//! #[derive(Debug, VariantEq)]
+//! enum E {
+//!     #[varianteq(deep)]
+//!     A(i32),
+//!     B(i32),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(E::A(1), E::A(1));
+//!     assert_ne!(E::A(1), E::A(2));
+//!     assert_eq!(E::B(1), E::B(2));
+//! }
+//! ```
+//!
+//! # `VariantHash`
+//!
+//! `VariantHash` hashes only the discriminant, so it agrees with `VariantEq`'s equality and the
+//! two can be combined on `HashMap`/`HashSet` keys.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate varianteq;
+//!
+//! use std::collections::HashSet;
+//!
+//! #[derive(Debug, VariantEq, VariantHash)]
+//! enum E {
+//!     A(i32),
+//!     B(i32),
+//! }
+//!
+//! fn main() {
+//!     let mut set = HashSet::new();
+//!     set.insert(E::A(1));
+//!     assert!(set.contains(&E::A(2)));
+//! }
+//! ```
+//!
+//! # `VariantOrd`
+//!
+//! `VariantOrd` orders enum values by which variant is active, in declaration order, ignoring
+//! field values.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate varianteq;
+//!
+//! #[derive(Debug, VariantEq, VariantOrd)]
+//! enum E {
+//!     A(i32),
+//!     B(i32),
+//! }
+//!
+//! fn main() {
+//!     assert!(E::A(100) < E::B(0));
+//! }
+//! ```
+//!
+//! # `VariantInfo`
+//!
+//! `VariantInfo` generates an `is_*()` predicate per variant plus a `variant_name()` method.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate varianteq;
+//!
+//! #[derive(Debug, VariantInfo)]
+//! enum E {
+//!     A(i32),
+//!     B(i32),
+//! }
+//!
+//! fn main() {
+//!     assert!(E::A(1).is_a());
+//!     assert!(!E::A(1).is_b());
+//!     assert_eq!(E::A(1).variant_name(), "A");
+//! }
+//! ```
+//!
+//! # `VariantDisplay` and `VariantFromStr`
+//!
+//! `VariantDisplay` prints a variant's name, and `VariantFromStr` parses it back, building any
+//! fields with `Default::default()`. `#[varianteq(rename = "...")]` overrides the matched/printed
+//! name for a single variant.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate varianteq;
+//!
+//! use std::str::FromStr;
+//!
+//! #[derive(Debug, VariantEq, VariantDisplay, VariantFromStr)]
+//! enum E {
+//!     A(i32),
+//!     #[varianteq(rename = "Bee")]
+//!     B(i32),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(E::A(1).to_string(), "A");
+//!     assert_eq!(E::B(1).to_string(), "Bee");
+//!     assert_eq!(E::from_str("Bee").unwrap(), E::B(0));
+//!     assert!(E::from_str("nope").is_err());
+//! }
+//! ```
+//!
 //! # Errors
 //!
 //! The `VariantEq` macro only applies to enums and will cauase a compilation error if used on
@@ -53,36 +168,436 @@
 //! ```text
 //! error: #[derive(VariantEq)] is only defined for enums
 //! ```
+//!
+//! It also rejects enums with zero variants, since discriminant equality would be vacuous.
+//!
+//! ```compile_fail
+//! # #[macro_use]
+//! # extern crate varianteq;
+//! #
+//! #[derive(VariantEq)]
+//! enum Never {}
+//! #
+//! # fn main() {}
+//! ```
+//!
+//! ```text
+//! error: #[derive(VariantEq)] cannot be derived for an enum with no variants
+//! ```
 
 use syn::{parse2};
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{Data, DeriveInput};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Lit, Meta, NestedMeta, Variant};
 
 extern crate proc_macro;
 
-#[proc_macro_derive(VariantEq)]
+#[proc_macro_derive(VariantEq, attributes(varianteq))]
 pub fn varianteq_derive(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input = parse2(tokens.into()).unwrap();
+    let input = match parse2::<DeriveInput>(tokens.into()) {
+        Ok(input) => input,
+        Err(err) => return proc_macro::TokenStream::from(err.to_compile_error()),
+    };
     proc_macro::TokenStream::from(derive(input))
 }
 
+/// Returns the enum's variants, or a spanned compile error if `item` is not an enum, to be
+/// surfaced via `to_compile_error()` instead of panicking inside the compiler.
+fn require_enum<'a>(item: &'a DeriveInput, macro_name: &str) -> Result<&'a syn::DataEnum, syn::Error> {
+    match &item.data {
+        Data::Enum(data_enum) => Ok(data_enum),
+        _ => Err(syn::Error::new_spanned(
+            &item.ident,
+            format!("#[derive({})] is only defined for enums", macro_name),
+        )),
+    }
+}
+
+/// Builds a pattern matching `variant` while ignoring its fields, e.g. `Self::V { .. }`,
+/// `Self::V(..)`, or `Self::V`, shaped to the variant's `Fields` kind.
+fn wildcard_pattern(variant_ident: &syn::Ident, fields: &Fields) -> proc_macro2::TokenStream {
+    match fields {
+        Fields::Named(_) => quote! { Self::#variant_ident { .. } },
+        Fields::Unnamed(_) => quote! { Self::#variant_ident(..) },
+        Fields::Unit => quote! { Self::#variant_ident },
+    }
+}
+
+/// Returns `true` if `variant` carries `#[varianteq(deep)]`, marking it for real field equality
+/// instead of the usual discriminant-only comparison.
+fn is_deep_variant(variant: &Variant) -> bool {
+    variant.attrs.iter().any(|attr| {
+        if !attr.path.is_ident("varianteq") {
+            return false;
+        }
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.iter().any(|nested| {
+                matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("deep"))
+            }),
+            _ => false,
+        }
+    })
+}
 
 fn derive(item: DeriveInput) -> proc_macro2::TokenStream {
-    match item.data {
-        Data::Enum(_) => (),
-        _ => unimplemented!("#[derive(VariantEq)] is only defined for enums"),
+    let data_enum = match require_enum(&item, "VariantEq") {
+        Ok(data_enum) => data_enum,
+        Err(err) => return err.to_compile_error(),
     };
 
-    let ident = item.ident;
-    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    if data_enum.variants.is_empty() {
+        return syn::Error::new_spanned(
+            &item.ident,
+            "#[derive(VariantEq)] cannot be derived for an enum with no variants",
+        )
+        .to_compile_error();
+    }
+
+    let ident = item.ident.clone();
+
+    if !data_enum.variants.iter().any(is_deep_variant) {
+        let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+        return quote! {
+            impl #impl_generics PartialEq for #ident #ty_generics #where_clause {
+                fn eq(&self, other: &#ident#ty_generics) -> bool {
+                    ::std::mem::discriminant(self) == ::std::mem::discriminant(other)
+                }
+            }
+            impl #impl_generics Eq for #ident #ty_generics #where_clause {}
+        };
+    }
+
+    let mut generics = item.generics.clone();
+    let mut arms = Vec::new();
+
+    for variant in &data_enum.variants {
+        let variant_ident = &variant.ident;
+        if is_deep_variant(variant) {
+            match &variant.fields {
+                Fields::Named(fields) => {
+                    let names: Vec<_> = fields.named.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+                    let self_bindings: Vec<_> = names.iter().map(|n| format_ident!("self_{}", n)).collect();
+                    let other_bindings: Vec<_> = names.iter().map(|n| format_ident!("other_{}", n)).collect();
+                    for field in &fields.named {
+                        let ty = &field.ty;
+                        generics.make_where_clause().predicates.push(syn::parse_quote!(#ty: ::std::cmp::Eq));
+                    }
+                    arms.push(quote! {
+                        (Self::#variant_ident { #(#names: #self_bindings),* }, Self::#variant_ident { #(#names: #other_bindings),* }) => {
+                            #(#self_bindings == #other_bindings)&&*
+                        }
+                    });
+                }
+                Fields::Unnamed(fields) => {
+                    let self_bindings: Vec<_> = (0..fields.unnamed.len()).map(|i| format_ident!("self_{}", i)).collect();
+                    let other_bindings: Vec<_> = (0..fields.unnamed.len()).map(|i| format_ident!("other_{}", i)).collect();
+                    for field in &fields.unnamed {
+                        let ty = &field.ty;
+                        generics.make_where_clause().predicates.push(syn::parse_quote!(#ty: ::std::cmp::Eq));
+                    }
+                    arms.push(quote! {
+                        (Self::#variant_ident(#(#self_bindings),*), Self::#variant_ident(#(#other_bindings),*)) => {
+                            #(#self_bindings == #other_bindings)&&*
+                        }
+                    });
+                }
+                Fields::Unit => {
+                    arms.push(quote! {
+                        (Self::#variant_ident, Self::#variant_ident) => true
+                    });
+                }
+            }
+        } else {
+            let pattern = wildcard_pattern(variant_ident, &variant.fields);
+            arms.push(quote! {
+                (#pattern, #pattern) => true
+            });
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     quote! {
         impl #impl_generics PartialEq for #ident #ty_generics #where_clause {
             fn eq(&self, other: &#ident#ty_generics) -> bool {
-                ::std::mem::discriminant(self) == ::std::mem::discriminant(other)
+                match (self, other) {
+                    #(#arms,)*
+                    _ => false,
+                }
             }
         }
         impl #impl_generics Eq for #ident #ty_generics #where_clause {}
     }
 }
+
+/// Implements `Hash` for an enum by hashing only `std::mem::discriminant(self)`, so that it
+/// agrees with the discriminant-only equality produced by `#[derive(VariantEq)]`.
+///
+/// Deriving both on the same enum keeps the `k1 == k2 => hash(k1) == hash(k2)` invariant that a
+/// plain `#[derive(Hash)]` (which also hashes field values) would violate.
+#[proc_macro_derive(VariantHash)]
+pub fn varianthash_derive(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse2::<DeriveInput>(tokens.into()) {
+        Ok(input) => input,
+        Err(err) => return proc_macro::TokenStream::from(err.to_compile_error()),
+    };
+    proc_macro::TokenStream::from(derive_hash(input))
+}
+
+fn derive_hash(item: DeriveInput) -> proc_macro2::TokenStream {
+    if let Err(err) = require_enum(&item, "VariantHash") {
+        return err.to_compile_error();
+    }
+
+    let ident = item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::std::hash::Hash for #ident #ty_generics #where_clause {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                ::std::mem::discriminant(self).hash(state)
+            }
+        }
+    }
+}
+
+/// Implements `PartialOrd`/`Ord` for an enum by comparing only which variant is active, in the
+/// order the variants are declared in source — the ordering analog of `VariantEq`'s
+/// discriminant-only equality.
+#[proc_macro_derive(VariantOrd)]
+pub fn variantord_derive(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse2::<DeriveInput>(tokens.into()) {
+        Ok(input) => input,
+        Err(err) => return proc_macro::TokenStream::from(err.to_compile_error()),
+    };
+    proc_macro::TokenStream::from(derive_ord(input))
+}
+
+fn derive_ord(item: DeriveInput) -> proc_macro2::TokenStream {
+    let data_enum = match require_enum(&item, "VariantOrd") {
+        Ok(data_enum) => data_enum,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let ident = item.ident.clone();
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let arms = data_enum.variants.iter().enumerate().map(|(index, variant)| {
+        let pattern = wildcard_pattern(&variant.ident, &variant.fields);
+        quote! { #pattern => #index }
+    });
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            fn __varianteq_variant_index(&self) -> usize {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::cmp::PartialOrd for #ident #ty_generics #where_clause {
+            fn partial_cmp(&self, other: &#ident#ty_generics) -> ::std::option::Option<::std::cmp::Ordering> {
+                ::std::option::Option::Some(self.cmp(other))
+            }
+        }
+
+        impl #impl_generics ::std::cmp::Ord for #ident #ty_generics #where_clause {
+            fn cmp(&self, other: &#ident#ty_generics) -> ::std::cmp::Ordering {
+                self.__varianteq_variant_index().cmp(&other.__varianteq_variant_index())
+            }
+        }
+    }
+}
+
+/// Converts a `CamelCase` identifier into `snake_case`, e.g. for building `is_*` method names
+/// from variant idents.
+fn to_snake_case(ident: &str) -> String {
+    let mut snake = String::with_capacity(ident.len());
+    for (i, c) in ident.char_indices() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Generates, for each variant, an `is_*()` predicate and a `variant_name()` method returning the
+/// variant's name as a `&'static str`, so enums combining `VariantEq` can be tested and named
+/// without hand-written matches.
+#[proc_macro_derive(VariantInfo)]
+pub fn variantinfo_derive(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse2::<DeriveInput>(tokens.into()) {
+        Ok(input) => input,
+        Err(err) => return proc_macro::TokenStream::from(err.to_compile_error()),
+    };
+    proc_macro::TokenStream::from(derive_info(input))
+}
+
+fn derive_info(item: DeriveInput) -> proc_macro2::TokenStream {
+    let data_enum = match require_enum(&item, "VariantInfo") {
+        Ok(data_enum) => data_enum,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let ident = item.ident.clone();
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let predicates = data_enum.variants.iter().map(|variant| {
+        let variant_ident = &variant.ident;
+        let method_ident = format_ident!("is_{}", to_snake_case(&variant_ident.to_string()));
+        let pattern = wildcard_pattern(variant_ident, &variant.fields);
+        quote! {
+            pub fn #method_ident(&self) -> bool {
+                matches!(self, #pattern)
+            }
+        }
+    });
+
+    let name_arms = data_enum.variants.iter().map(|variant| {
+        let name = variant.ident.to_string();
+        let pattern = wildcard_pattern(&variant.ident, &variant.fields);
+        quote! { #pattern => #name }
+    });
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#predicates)*
+
+            pub fn variant_name(&self) -> &'static str {
+                match self {
+                    #(#name_arms,)*
+                }
+            }
+        }
+    }
+}
+
+/// Returns the string a variant should be matched/printed as: the value of
+/// `#[varianteq(rename = "...")]` if present, otherwise the variant's own ident.
+fn variant_string_name(variant: &Variant) -> String {
+    variant
+        .attrs
+        .iter()
+        .find_map(|attr| {
+            if !attr.path.is_ident("varianteq") {
+                return None;
+            }
+            match attr.parse_meta() {
+                Ok(Meta::List(list)) => list.nested.iter().find_map(|nested| match nested {
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename") => {
+                        match &nv.lit {
+                            Lit::Str(s) => Some(s.value()),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }),
+                _ => None,
+            }
+        })
+        .unwrap_or_else(|| variant.ident.to_string())
+}
+
+/// Implements `Display` for an enum, printing each variant's name (ignoring field values), so
+/// enums combining `VariantEq` can round-trip through their variant names with `VariantFromStr`.
+#[proc_macro_derive(VariantDisplay, attributes(varianteq))]
+pub fn variantdisplay_derive(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse2::<DeriveInput>(tokens.into()) {
+        Ok(input) => input,
+        Err(err) => return proc_macro::TokenStream::from(err.to_compile_error()),
+    };
+    proc_macro::TokenStream::from(derive_display(input))
+}
+
+fn derive_display(item: DeriveInput) -> proc_macro2::TokenStream {
+    let data_enum = match require_enum(&item, "VariantDisplay") {
+        Ok(data_enum) => data_enum,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let ident = item.ident.clone();
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let arms = data_enum.variants.iter().map(|variant| {
+        let name = variant_string_name(variant);
+        let pattern = wildcard_pattern(&variant.ident, &variant.fields);
+        quote! { #pattern => ::std::write!(f, "{}", #name) }
+    });
+
+    quote! {
+        impl #impl_generics ::std::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+    }
+}
+
+/// Implements `FromStr` for an enum, constructing the variant whose (possibly renamed) name
+/// matches the input string and filling its fields with `Default::default()`.
+#[proc_macro_derive(VariantFromStr, attributes(varianteq))]
+pub fn variantfromstr_derive(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse2::<DeriveInput>(tokens.into()) {
+        Ok(input) => input,
+        Err(err) => return proc_macro::TokenStream::from(err.to_compile_error()),
+    };
+    proc_macro::TokenStream::from(derive_fromstr(input))
+}
+
+fn derive_fromstr(item: DeriveInput) -> proc_macro2::TokenStream {
+    let data_enum = match require_enum(&item, "VariantFromStr") {
+        Ok(data_enum) => data_enum,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let ident = item.ident.clone();
+    let mut generics = item.generics.clone();
+
+    let arms: Vec<_> = data_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_ident = &variant.ident;
+            let name = variant_string_name(variant);
+            let constructor = match &variant.fields {
+                Fields::Named(fields) => {
+                    let names: Vec<_> = fields.named.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+                    for field in &fields.named {
+                        let ty = &field.ty;
+                        generics.make_where_clause().predicates.push(syn::parse_quote!(#ty: ::std::default::Default));
+                    }
+                    quote! { Self::#variant_ident { #(#names: ::std::default::Default::default()),* } }
+                }
+                Fields::Unnamed(fields) => {
+                    let defaults = fields.unnamed.iter().map(|field| {
+                        let ty = &field.ty;
+                        generics.make_where_clause().predicates.push(syn::parse_quote!(#ty: ::std::default::Default));
+                        quote! { ::std::default::Default::default() }
+                    });
+                    quote! { Self::#variant_ident(#(#defaults),*) }
+                }
+                Fields::Unit => quote! { Self::#variant_ident },
+            };
+            quote! { #name => ::std::result::Result::Ok(#constructor) }
+        })
+        .collect();
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::std::str::FromStr for #ident #ty_generics #where_clause {
+            type Err = ::std::string::String;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                match s {
+                    #(#arms,)*
+                    other => ::std::result::Result::Err(::std::string::String::from(other)),
+                }
+            }
+        }
+    }
+}